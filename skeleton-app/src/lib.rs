@@ -23,6 +23,20 @@ use hyperware_process_lib::{
 // Standard imports for serialization
 use serde::{Deserialize, Serialize};
 
+// Needed for the Tower-style `Layer` trait below: it declares `async fn`s
+// in a trait used as a trait object (`&dyn Layer`), which plain Rust can't do yet.
+// Add `async-trait` to this crate's Cargo.toml to build this.
+use async_trait::async_trait;
+// Needed for real jitter in the delivery queue's backoff (see
+// `delivery_backoff_secs`/`process_outbound_queue`). Add `rand` to this
+// crate's Cargo.toml to build this.
+use rand::Rng;
+// The keyed MAC in `sign_envelope`/`verify_envelope` below uses the
+// `hmac` and `sha2` crates by path (`hmac::`/`sha2::`), so they need no
+// `use` here. Add `hmac` and `sha2` to this crate's Cargo.toml to build this.
+
+use std::collections::HashMap;
+
 // STEP 1: DEFINE YOUR APP STATE
 // This struct holds all persistent data for your app
 // It MUST derive Default, Serialize, and Deserialize
@@ -35,6 +49,354 @@ pub struct AppState {
     // For P2P apps, you might store:
     // my_node_id: Option<String>,
     // connected_nodes: Vec<String>,
+
+    // Durable outbound delivery queue (see `send_to_node_durable` below).
+    // Persisted along with the rest of AppState per `save_config`, so
+    // undelivered messages survive a process restart instead of being
+    // dropped on the floor like the one-shot `send_to_node` below.
+    outbound_queue: Vec<QueuedDelivery>,
+    dead_letters: Vec<QueuedDelivery>,
+
+    // Demonstrates the HashMap/rich-enum WIT bridging below: a native
+    // Rust HashMap of a data-carrying enum, kept as ordinary internal
+    // state (WIT limitations only bite at #[http]/#[remote] signatures,
+    // not on AppState's own fields).
+    user_profiles: HashMap<String, UserProfile>,
+
+    // Keyed-MAC secret for `sign_envelope`/`verify_envelope` (see
+    // `handle_remote_message` below). Must be configured (via
+    // `set_signing_secret`) to the same value on both sides of a
+    // conversation out-of-band before signed messages will verify -
+    // this skeleton has no access to the kernel's per-node networking
+    // key material, so it can't do real asymmetric node-identity signing.
+    shared_signing_secret: Option<Vec<u8>>,
+}
+
+// One pending P2P delivery. Tracked until it succeeds or exhausts its
+// retry budget, at which point it's moved from `outbound_queue` into
+// `dead_letters` for manual inspection via `get_dead_letters`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct QueuedDelivery {
+    target_node: String,
+    message: String,
+    attempts: u32,
+    next_attempt_at: u64, // unix seconds
+}
+
+// Backoff schedule: base 10s, doubling per attempt, capped at 10 minutes.
+// After MAX_ATTEMPTS failed tries an entry is dead-lettered rather than
+// retried forever.
+const DELIVERY_BASE_BACKOFF_SECS: u64 = 10;
+const DELIVERY_MAX_BACKOFF_SECS: u64 = 600;
+const DELIVERY_MAX_ATTEMPTS: u32 = 8;
+// Log a warning if a single delivery round-trip takes longer than this,
+// mirroring the federation crate's "activity sending is slow" check.
+const DELIVERY_SLOW_WARN_MS: u128 = 5_000;
+// How often the background worker below wakes up to drain the queue.
+const DELIVERY_TICK_INTERVAL_SECS: u64 = 15;
+
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn delivery_backoff_secs(attempts: u32) -> u64 {
+    let scaled = DELIVERY_BASE_BACKOFF_SECS.saturating_mul(1u64 << attempts.min(16));
+    scaled.min(DELIVERY_MAX_BACKOFF_SECS)
+}
+
+// Arms a one-shot wakeup with `timer:distro:sys`, which this process
+// receives back as a local request/response and handles in
+// `handle_delivery_tick` below. Fire-and-forget: we don't await the
+// timer's own response here, only the recurring tick it triggers.
+fn schedule_next_delivery_tick() {
+    let timer_process: Result<ProcessId, _> = "timer:distro:sys".parse();
+    let Ok(timer_process) = timer_process else {
+        return;
+    };
+    let timer_address = Address::new(our().node.clone(), timer_process);
+    let _ = Request::new()
+        .target(timer_address)
+        .body(serde_json::to_vec(&serde_json::json!({
+            "SetTimer": DELIVERY_TICK_INTERVAL_SECS * 1000
+        }))
+        .unwrap())
+        .send();
+}
+
+// Envelope carried over the wire for `handle_remote_message`. `signature`
+// is an HMAC-SHA256 over `(sender, payload)` keyed by `shared_signing_secret`
+// (see `sign_envelope`/`verify_envelope`), so the receiver can tell whether
+// `sender` was tampered with - or simply made up - in transit.
+#[derive(Serialize, Deserialize)]
+pub struct SignedEnvelope {
+    sender: String,
+    payload: String,
+    signature: String,
+}
+
+type HmacSha256 = hmac::Hmac<sha2::Sha256>;
+
+// Real keyed MAC: without knowing `secret`, a caller cannot produce a
+// `signature` that `verify_envelope` will accept for any `sender` they
+// choose, unlike an unkeyed hash (a fixed, publicly known algorithm),
+// which anyone could recompute for a forged `sender`. `secret` must be configured to the same value on both the
+// sending and receiving node via `set_signing_secret` - this skeleton has
+// no access to the kernel's per-node networking key material, so it
+// can't do real asymmetric node-identity signing; this is a pre-shared
+// symmetric secret standing in for it.
+fn sign_envelope(sender: &str, payload: &str, secret: &[u8]) -> String {
+    let mut mac = <HmacSha256 as hmac::Mac>::new_from_slice(secret).expect("HMAC accepts any key length");
+    hmac::Mac::update(&mut mac, sender.as_bytes());
+    hmac::Mac::update(&mut mac, b"\0");
+    hmac::Mac::update(&mut mac, payload.as_bytes());
+    hex_encode(&hmac::Mac::finalize(mac).into_bytes())
+}
+
+fn verify_envelope(sender: &str, payload: &str, signature: &str, secret: &[u8]) -> bool {
+    let mut mac = <HmacSha256 as hmac::Mac>::new_from_slice(secret).expect("HMAC accepts any key length");
+    hmac::Mac::update(&mut mac, sender.as_bytes());
+    hmac::Mac::update(&mut mac, b"\0");
+    hmac::Mac::update(&mut mac, payload.as_bytes());
+    let expected = hmac::Mac::finalize(mac).into_bytes();
+
+    // Real constant-time comparison: decode the hex signature back to raw
+    // bytes and XOR-accumulate over every byte pair (no early return once
+    // lengths match), instead of `==` on the hex strings, which short-
+    // circuits at the first differing byte and can leak timing info about
+    // how much of a guessed signature was correct.
+    match hex_decode(signature) {
+        Some(provided) => constant_time_eq(&expected, &provided),
+        None => false,
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+fn hex_decode(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// HASHMAP / RICH-ENUM SUPPORT VIA TRANSPARENT JSON BRIDGING
+// The WIT type-compatibility notes at the bottom of this file say to
+// degrade `HashMap<K,V>` to `Vec<(K,V)>` and complex enums to JSON
+// strings by hand at every call site. `Role` below is exactly the kind of
+// type that forces that: a data-carrying enum inside a map.
+//
+// NOTE: a real `#[wit_json]` would be a derive/attribute macro that lets
+// a field keep this natural Rust type while the macro transparently wires
+// up the JSON-string WIT representation (serializing on the way out,
+// deserializing on the way in) at the #[hyperprocess] codegen boundary.
+// That derive would live in `hyperprocess_macro`, which isn't vendored
+// into this skeleton crate, so `wit_json_encode`/`wit_json_decode` below
+// are the hand-written equivalent - call them at the edge of any
+// #[http]/#[remote] handler that needs to move a `Role` (or any other
+// HashMap/rich-enum type) across the WIT boundary.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Role {
+    Admin,
+    Member { since: u32 },
+    Guest(String),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct UserProfile {
+    name: String,
+    roles: HashMap<String, Role>,
+}
+
+// The `#[wit_json]` path: keep the natural type, bridge via a JSON string.
+fn wit_json_encode<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string(value).map_err(|e| format!("wit_json encode failed: {}", e))
+}
+
+fn wit_json_decode<T: serde::de::DeserializeOwned>(encoded: &str) -> Result<T, String> {
+    serde_json::from_str(encoded).map_err(|e| format!("wit_json decode failed: {}", e))
+}
+
+// The WIT-native path: lower a HashMap to `list<tuple<K,V>>` instead of a
+// JSON string, for callers that want wire-native representation rather
+// than JSON fidelity.
+fn hashmap_to_wit_pairs<V: Clone>(map: &HashMap<String, V>) -> Vec<(String, V)> {
+    map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+fn wit_pairs_to_hashmap<V>(pairs: Vec<(String, V)>) -> HashMap<String, V> {
+    pairs.into_iter().collect()
+}
+
+// TOWER-STYLE MIDDLEWARE LAYERS
+// Cross-cutting concerns (auth, rate limiting, logging) shouldn't have to
+// be copy-pasted into every #[http]/#[remote] handler body. A `Layer` runs
+// `before` each matching endpoint (can short-circuit with an error
+// response) and `after` each one (can rewrite the response on the way
+// out), the same shape as Tower/Axum middleware.
+//
+// NOTE: in a full Hyperapp setup this would be wired in declaratively, via
+// a `layers = vec![...]` option on `#[hyperprocess]` (and/or a
+// `#[middleware]` attribute on individual handlers), with the macro
+// generating the before/after calls around each endpoint automatically.
+// That codegen lives in `hyperprocess_macro`, which isn't vendored into
+// this skeleton crate, so here the layers are applied by hand - see
+// `run_layers` and `admin_stats` below for the pattern to copy into any
+// handler you want to protect.
+#[async_trait]
+pub trait Layer: Send + Sync {
+    async fn before(&self, ctx: &mut LayerContext) -> Result<(), String>;
+
+    async fn after(&self, _ctx: &LayerContext, response: String) -> String {
+        response
+    }
+}
+
+// Per-call context threaded through a handler's layer stack.
+pub struct LayerContext {
+    endpoint: String,
+    caller: String,
+    started_at_secs: u64,
+}
+
+// Runs `before` on every layer in order, short-circuiting on the first
+// error; if all pass, runs `handler`, then `after` on every layer in
+// reverse order (outermost layer gets the final word on the response).
+async fn run_layers<F, Fut>(
+    layers: &[&dyn Layer],
+    mut ctx: LayerContext,
+    handler: F,
+) -> Result<String, String>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<String, String>>,
+{
+    for layer in layers {
+        layer.before(&mut ctx).await?;
+    }
+
+    let mut response = handler().await?;
+
+    for layer in layers.iter().rev() {
+        response = layer.after(&ctx, response).await;
+    }
+
+    Ok(response)
+}
+
+// Logs the endpoint name and wall-clock time taken.
+pub struct RequestLoggingLayer;
+
+#[async_trait]
+impl Layer for RequestLoggingLayer {
+    async fn before(&self, ctx: &mut LayerContext) -> Result<(), String> {
+        println!("[{}] -> {} called by {}", ctx.started_at_secs, ctx.endpoint, ctx.caller);
+        Ok(())
+    }
+
+    async fn after(&self, ctx: &LayerContext, response: String) -> String {
+        let elapsed = unix_now_secs().saturating_sub(ctx.started_at_secs);
+        println!("[{}] <- {} finished in {}s", ctx.started_at_secs, ctx.endpoint, elapsed);
+        response
+    }
+}
+
+// Simple token-bucket rate limiter keyed by `ctx.caller`. `buckets` maps
+// caller -> (tokens left, last refill time); refills `refill_per_sec`
+// tokens/sec up to `capacity`. It is not keyed by node id or IP:
+// `admin_stats`, its only user, puts the presented bearer token in
+// `caller`, so each distinct token gets its own bucket.
+pub struct RateLimitLayer {
+    capacity: f64,
+    refill_per_sec: f64,
+    buckets: std::sync::Mutex<HashMap<String, (f64, u64)>>,
+}
+
+impl RateLimitLayer {
+    pub fn new(capacity: f64, refill_per_sec: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+            buckets: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+// `admin_stats` needs one `RateLimitLayer` whose `buckets` persists
+// across calls, not a fresh one built (and dropped) inside the handler -
+// a limiter that's reconstructed every request can never see its own
+// past tokens consumed, so it can never run dry. A process-wide static
+// (rather than an AppState field) is the natural home for this: bucket
+// state is request-rate bookkeeping, not data worth persisting to disk
+// across restarts the way `save_config` persists AppState.
+static ADMIN_RATE_LIMITER: std::sync::OnceLock<RateLimitLayer> = std::sync::OnceLock::new();
+
+fn admin_rate_limiter() -> &'static RateLimitLayer {
+    ADMIN_RATE_LIMITER.get_or_init(|| RateLimitLayer::new(5.0, 0.5))
+}
+
+#[async_trait]
+impl Layer for RateLimitLayer {
+    async fn before(&self, ctx: &mut LayerContext) -> Result<(), String> {
+        let now = unix_now_secs();
+        let mut buckets = self.buckets.lock().unwrap();
+        let (tokens, last_refill) = buckets
+            .entry(ctx.caller.clone())
+            .or_insert((self.capacity, now));
+
+        let elapsed = now.saturating_sub(*last_refill) as f64;
+        *tokens = (*tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        *last_refill = now;
+
+        if *tokens < 1.0 {
+            return Err(format!("Rate limit exceeded for {}", ctx.caller));
+        }
+        *tokens -= 1.0;
+        Ok(())
+    }
+}
+
+// Short-circuits with an error unless `ctx.caller` (which `admin_stats`
+// fills with the presented bearer token) matches `expected_token`.
+pub struct BearerAuthLayer {
+    expected_token: String,
+}
+
+impl BearerAuthLayer {
+    pub fn new(expected_token: String) -> Self {
+        Self { expected_token }
+    }
+}
+
+#[async_trait]
+impl Layer for BearerAuthLayer {
+    async fn before(&self, ctx: &mut LayerContext) -> Result<(), String> {
+        // In a real deployment the bearer token would come from a request
+        // header; this skeleton's #[http] handlers only see `request_body`,
+        // so callers pass it as part of that body (see `admin_stats`).
+        if ctx.caller != self.expected_token {
+            return Err("Unauthorized: missing or invalid bearer token".to_string());
+        }
+        Ok(())
+    }
 }
 
 // STEP 2: IMPLEMENT YOUR APP LOGIC
@@ -47,10 +409,20 @@ pub struct AppState {
     ui = Some(HttpBindingConfig::default()),
     
     // HTTP API endpoints - MUST include /api for frontend communication
+    //
+    // /rpc is an opt-in JSON-RPC 2.0 endpoint (see the `rpc` handler below).
+    // It is a plain #[http] method, not a macro-level dispatch mode: the
+    // hyperprocess_macro in this skeleton only knows about the ad-hoc
+    // `{ "MethodName": params }` envelope, so JSON-RPC framing is handled
+    // by hand inside `rpc` rather than by per-method endpoint config.
     endpoints = vec![
-        Binding::Http { 
-            path: "/api", 
-            config: HttpBindingConfig::new(false, false, false, None) 
+        Binding::Http {
+            path: "/api",
+            config: HttpBindingConfig::new(false, false, false, None)
+        },
+        Binding::Http {
+            path: "/rpc",
+            config: HttpBindingConfig::new(false, false, false, None)
         }
     ],
     
@@ -83,6 +455,13 @@ impl AppState {
         // Get our node identity (useful for P2P apps)
         let our_node = our().node.clone();
         println!("Skeleton app initialized on node: {}", our_node);
+
+        // Arm the recurring background worker for the durable outbound
+        // queue (see `handle_delivery_tick` below). This fires once here
+        // and re-arms itself on every tick, so the queue drains on a
+        // schedule instead of only when something happens to poke
+        // `send_to_node_durable` or `tick_outbound_queue`.
+        schedule_next_delivery_tick();
     }
     
     // HTTP ENDPOINT EXAMPLE
@@ -123,19 +502,208 @@ impl AppState {
         serde_json::to_string(&self.messages).unwrap_or_else(|_| "[]".to_string())
     }
     
-    // REMOTE ENDPOINT EXAMPLE
-    // These are called by other nodes in the P2P network
-    // Use #[remote] instead of #[http]
+    // REMOTE ENDPOINT EXAMPLE - VERIFIED SENDER
+    // These are called by other nodes in the P2P network (use #[remote]
+    // instead of #[http]). A caller's own payload saying
+    // `sender_node: "alice.os"` would just be a string, trivially
+    // spoofable by anyone who can reach this process - so instead of
+    // taking a raw message, this handler requires a `SignedEnvelope`
+    // whose `signature` is a keyed MAC over `(sender, payload)` (see
+    // `sign_envelope`/`verify_envelope`) and rejects anything that
+    // doesn't check out, giving the handler body a `sender` it can
+    // actually trust. `send_to_node` below is this handler's matching
+    // signed sender.
     #[remote]
-    async fn handle_remote_message(&mut self, message: String) -> Result<String, String> {
-        // Store the message
-        // Note: In remote handlers, you can't easily get the sender's node ID
-        // You would need to include it in the message payload
-        self.messages.push(format!("Remote message: {}", message));
-        
-        Ok("Message received".to_string())
+    async fn handle_remote_message(&mut self, envelope: String) -> Result<String, String> {
+        let envelope: SignedEnvelope = serde_json::from_str(&envelope)
+            .map_err(|e| format!("Invalid envelope: {}", e))?;
+
+        let secret = self
+            .shared_signing_secret
+            .as_deref()
+            .ok_or_else(|| "No signing secret configured; call set_signing_secret first".to_string())?;
+
+        if !verify_envelope(&envelope.sender, &envelope.payload, &envelope.signature, secret) {
+            return Err(format!(
+                "Signature verification failed for claimed sender '{}'",
+                envelope.sender
+            ));
+        }
+
+        self.messages.push(format!(
+            "Verified message from {}: {}",
+            envelope.sender, envelope.payload
+        ));
+
+        Ok(format!("Message received from verified sender {}", envelope.sender))
     }
-    
+
+    // Configures the pre-shared secret used by `sign_envelope`/
+    // `verify_envelope`. Operators on both sides of a conversation must
+    // set this to the same value out-of-band before signed messages
+    // will verify - see the NOTE on `SignedEnvelope`/`sign_envelope`.
+    #[http]
+    async fn set_signing_secret(&mut self, request_body: String) -> Result<String, String> {
+        let secret: String =
+            serde_json::from_str(&request_body).map_err(|e| format!("Invalid request: {}", e))?;
+        self.shared_signing_secret = Some(secret.into_bytes());
+        Ok("Signing secret configured".to_string())
+    }
+
+    // JSON-RPC 2.0 ENDPOINT
+    // Handles JSON-RPC 2.0 framing (https://www.jsonrpc.org/specification)
+    // inside this skeleton's ad-hoc envelope: like every #[http] method it
+    // is reached as `{ "Rpc": "<json-rpc request text>" }`, and a bare
+    // JSON-RPC object posted to /rpc is not dispatched to it.
+    // Supports single requests, batches, and notifications (no "id" ->
+    // executed but no response entry). Each JSON-RPC "method" maps to one
+    // of the #[http] handlers in this impl; "params" is forwarded to the handler
+    // as if it were the `request_body` of the ad-hoc envelope, so the same
+    // positional-array-vs-object rules apply.
+    #[http]
+    async fn rpc(&mut self, request_body: String) -> String {
+        let parsed: serde_json::Value = match serde_json::from_str(&request_body) {
+            Ok(val) => val,
+            Err(e) => {
+                return serde_json::to_string(&Self::jsonrpc_error(
+                    serde_json::Value::Null,
+                    -32700,
+                    &format!("Parse error: {}", e),
+                ))
+                .unwrap();
+            }
+        };
+
+        match parsed {
+            serde_json::Value::Array(batch) => {
+                let mut responses = Vec::new();
+                for entry in batch {
+                    if let Some(resp) = self.handle_jsonrpc_entry(entry).await {
+                        responses.push(resp);
+                    }
+                }
+                serde_json::to_string(&responses).unwrap_or_else(|_| "[]".to_string())
+            }
+            entry => match self.handle_jsonrpc_entry(entry).await {
+                Some(resp) => serde_json::to_string(&resp).unwrap(),
+                None => String::new(),
+            },
+        }
+    }
+
+    // Helper: apply one JSON-RPC request object, returning `None` for
+    // notifications (no "id" field), which must execute but get no reply.
+    async fn handle_jsonrpc_entry(
+        &mut self,
+        entry: serde_json::Value,
+    ) -> Option<serde_json::Value> {
+        let id = entry.get("id").cloned();
+        let method = match entry.get("method").and_then(|m| m.as_str()) {
+            Some(m) => m.to_string(),
+            None => {
+                let id = id.unwrap_or(serde_json::Value::Null);
+                return Some(Self::jsonrpc_error(id, -32600, "Invalid Request"));
+            }
+        };
+        let params = entry.get("params").cloned().unwrap_or(serde_json::Value::Null);
+
+        let result = self.dispatch_jsonrpc_method(&method, params).await;
+
+        // No "id" => this was a notification; execute but send no response.
+        let id = id?;
+        Some(match result {
+            Ok(value) => serde_json::json!({
+                "jsonrpc": "2.0",
+                "result": value,
+                "id": id,
+            }),
+            Err((code, message)) => Self::jsonrpc_error(id, code, &message),
+        })
+    }
+
+    // Helper: route a JSON-RPC method name to the matching #[http] handler
+    // and fold its result (or error) into a serde_json::Value.
+    async fn dispatch_jsonrpc_method(
+        &mut self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, (i64, String)> {
+        let request_body = serde_json::to_string(&params).unwrap_or_else(|_| "null".to_string());
+
+        // NOTE: this list must stay in sync by hand with the #[http]
+        // methods in this impl - there's no macro-level reflection over them in
+        // this skeleton, so every new #[http] handler needs a matching
+        // arm here to actually be reachable over JSON-RPC.
+        match method {
+            "get_status" => Ok(serde_json::Value::String(self.get_status(request_body).await)),
+            "increment_counter" => self
+                .increment_counter(request_body)
+                .await
+                .map(|count| serde_json::json!(count))
+                .map_err(|e| (-32000, e)),
+            "get_messages" => Ok(serde_json::Value::String(self.get_messages(request_body).await)),
+            "send_to_node" => self
+                .send_to_node(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            "send_to_node_durable" => self
+                .send_to_node_durable(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            "tick_outbound_queue" => {
+                Ok(serde_json::Value::String(self.tick_outbound_queue(request_body).await))
+            }
+            "get_dead_letters" => {
+                Ok(serde_json::Value::String(self.get_dead_letters(request_body).await))
+            }
+            "set_signing_secret" => self
+                .set_signing_secret(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            "admin_stats" => self
+                .admin_stats(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            "set_user_role" => self
+                .set_user_role(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            "get_user_profile" => self
+                .get_user_profile(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            "get_user_roles_native" => self
+                .get_user_roles_native(request_body)
+                .await
+                .map(|pairs| serde_json::json!(pairs))
+                .map_err(|e| (-32000, e)),
+            "set_user_roles_native" => self
+                .set_user_roles_native(request_body)
+                .await
+                .map(serde_json::Value::String)
+                .map_err(|e| (-32000, e)),
+            _ => Err((-32601, format!("Method not found: {}", method))),
+        }
+    }
+
+    // Helper: build a `{"jsonrpc":"2.0","error":{...},"id":...}` response.
+    fn jsonrpc_error(id: serde_json::Value, code: i64, message: &str) -> serde_json::Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "error": {
+                "code": code,
+                "message": message,
+            },
+            "id": id,
+        })
+    }
+
     // P2P COMMUNICATION EXAMPLE
     // Shows how to send messages to other nodes
     #[http]
@@ -146,23 +714,36 @@ impl AppState {
             target_node: String,
             message: String,
         }
-        
+
         let req: SendRequest = serde_json::from_str(&request_body)
             .map_err(|e| format!("Invalid request: {}", e))?;
-        
+
+        let secret = self
+            .shared_signing_secret
+            .as_deref()
+            .ok_or_else(|| "No signing secret configured; call set_signing_secret first".to_string())?;
+        let sender = our().node.clone();
+        let envelope = SignedEnvelope {
+            signature: sign_envelope(&sender, &req.message, secret),
+            sender,
+            payload: req.message,
+        };
+
         // Construct the target address
         // Format: "process-name:package-name:publisher"
         let target_process_id = "skeleton-app:skeleton-app:skeleton.os"
             .parse::<ProcessId>()
             .map_err(|e| format!("Invalid process ID: {}", e))?;
-        
+
         let target_address = Address::new(req.target_node, target_process_id);
-        
-        // Create request wrapper for remote method
+
+        // Create request wrapper for remote method - `handle_remote_message`
+        // expects a signed `SignedEnvelope`, not a raw message, so it can
+        // authenticate `sender` instead of trusting an unauthenticated field.
         let request_wrapper = serde_json::json!({
-            "HandleRemoteMessage": req.message
+            "HandleRemoteMessage": serde_json::to_string(&envelope).unwrap()
         });
-        
+
         // Send the request
         // CRITICAL: Always set expects_response timeout for remote calls
         let result = Request::new()
@@ -170,12 +751,384 @@ impl AppState {
             .body(serde_json::to_vec(&request_wrapper).unwrap())
             .expects_response(30) // 30 second timeout
             .send_and_await_response(30);
-        
+
         match result {
             Ok(_) => Ok("Message sent successfully".to_string()),
             Err(e) => Err(format!("Failed to send message: {:?}", e))
         }
     }
+
+    // DURABLE P2P DELIVERY
+    // Unlike `send_to_node` above, this does not fail when the peer is
+    // unreachable right now: it persists the message into `outbound_queue`
+    // (saved with the rest of AppState per `save_config`), then makes one
+    // delivery pass over the due entries before returning (each attempt
+    // waits up to 30s for a response). Delivery happens via
+    // `process_outbound_queue`, which
+    // retries with exponential backoff and eventually dead-letters
+    // messages that never get through.
+    #[http]
+    async fn send_to_node_durable(&mut self, request_body: String) -> Result<String, String> {
+        #[derive(Deserialize)]
+        struct SendRequest {
+            target_node: String,
+            message: String,
+        }
+
+        let req: SendRequest = serde_json::from_str(&request_body)
+            .map_err(|e| format!("Invalid request: {}", e))?;
+
+        // `process_outbound_queue` signs at send time (it needs a fresh
+        // `our().node` and the current secret anyway), so the queue only
+        // needs to remember the plaintext message.
+        self.outbound_queue.push(QueuedDelivery {
+            target_node: req.target_node,
+            message: req.message,
+            attempts: 0,
+            next_attempt_at: unix_now_secs(),
+        });
+
+        // Give the queue an immediate chance to drain rather than waiting
+        // for the next scheduled tick.
+        self.process_outbound_queue().await;
+
+        Ok("Message enqueued for durable delivery".to_string())
+    }
+
+    // BACKGROUND WORKER
+    // Handles the wakeup armed by `schedule_next_delivery_tick` (called
+    // from `#[init]` and again at the end of this handler), draining the
+    // outbound queue on a fixed schedule instead of only when an HTTP
+    // caller happens to hit `send_to_node_durable`/`tick_outbound_queue`.
+    // `#[local]` is this skeleton's handler for requests from other
+    // processes on the same node - which is what `timer:distro:sys`'s
+    // wakeup arrives as.
+    #[local]
+    async fn handle_delivery_tick(&mut self, _request_body: String) -> Result<String, String> {
+        self.process_outbound_queue().await;
+        schedule_next_delivery_tick();
+        Ok("tick processed".to_string())
+    }
+
+    // Drains due entries from `outbound_queue`, attempting delivery and
+    // rescheduling on failure with exponential backoff + the jitter below.
+    async fn process_outbound_queue(&mut self) {
+        let now = unix_now_secs();
+        let target_process_id = match "skeleton-app:skeleton-app:skeleton.os".parse::<ProcessId>() {
+            Ok(id) => id,
+            Err(_) => return,
+        };
+
+        // `handle_remote_message` on the other end now requires a signed
+        // `SignedEnvelope` (see `send_to_node`): without a secret
+        // configured there's nothing this worker can do but wait, so
+        // leave the queue untouched rather than burning through retries
+        // or losing messages on a guaranteed rejection.
+        let Some(secret) = self.shared_signing_secret.clone() else {
+            if !self.outbound_queue.is_empty() {
+                println!(
+                    "Outbound queue has {} pending entries but no signing secret is configured; call set_signing_secret",
+                    self.outbound_queue.len()
+                );
+            }
+            return;
+        };
+        let sender = our().node.clone();
+
+        let due: Vec<usize> = self
+            .outbound_queue
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.next_attempt_at <= now)
+            .map(|(i, _)| i)
+            .collect();
+
+        // Process in reverse so swap_remove below doesn't disturb indices
+        // we still need to visit.
+        for i in due.into_iter().rev() {
+            let mut entry = self.outbound_queue.swap_remove(i);
+
+            let target_address = Address::new(entry.target_node.clone(), target_process_id.clone());
+            let envelope = SignedEnvelope {
+                signature: sign_envelope(&sender, &entry.message, &secret),
+                sender: sender.clone(),
+                payload: entry.message.clone(),
+            };
+            let request_wrapper = serde_json::json!({
+                "HandleRemoteMessage": serde_json::to_string(&envelope).unwrap()
+            });
+
+            let started = std::time::Instant::now();
+            let transport_result = Request::new()
+                .target(target_address)
+                .body(serde_json::to_vec(&request_wrapper).unwrap())
+                .expects_response(30)
+                .send_and_await_response(30);
+            let elapsed_ms = started.elapsed().as_millis();
+            if elapsed_ms > DELIVERY_SLOW_WARN_MS {
+                println!(
+                    "WARNING: delivery to {} took {}ms (> {}ms threshold)",
+                    entry.target_node, elapsed_ms, DELIVERY_SLOW_WARN_MS
+                );
+            }
+
+            // A successful round-trip only means the transport worked -
+            // the receiver still embeds its own `Result<String, String>`
+            // in the response body (rejected signature, missing secret,
+            // etc.), which must be treated as a delivery failure too, not
+            // silently dropped as if it had succeeded.
+            let delivery_result: Result<String, String> = match transport_result {
+                Ok(response) => serde_json::from_slice::<Result<String, String>>(response.body())
+                    .unwrap_or_else(|e| Err(format!("Failed to decode response: {}", e))),
+                Err(e) => Err(format!("Transport error: {:?}", e)),
+            };
+
+            match delivery_result {
+                Ok(_) => {} // delivered; entry was already removed from the queue
+                Err(e) => {
+                    entry.attempts += 1;
+                    if entry.attempts >= DELIVERY_MAX_ATTEMPTS {
+                        println!(
+                            "Delivery to {} failed after {} attempts ({}); dead-lettering",
+                            entry.target_node, entry.attempts, e
+                        );
+                        self.dead_letters.push(entry);
+                    } else {
+                        // Full jitter: spread retries within [0, backoff] so
+                        // a burst of failures doesn't retry in lockstep.
+                        // Must be real randomness, not a function of
+                        // `attempts`/`next_attempt_at` alone - those are
+                        // often identical across entries that fail in the
+                        // same tick, which would reschedule them all to
+                        // the same instant.
+                        let backoff = delivery_backoff_secs(entry.attempts);
+                        let jitter = rand::thread_rng().gen_range(0..=backoff);
+                        entry.next_attempt_at = now + jitter;
+                        self.outbound_queue.push(entry);
+                    }
+                }
+            }
+        }
+    }
+
+    // Manual drain of the outbound queue, e.g. for an operator who doesn't
+    // want to wait for the next `handle_delivery_tick` wakeup.
+    #[http]
+    async fn tick_outbound_queue(&mut self, _request_body: String) -> String {
+        self.process_outbound_queue().await;
+        format!(
+            "{{\"pending\":{},\"dead_letters\":{}}}",
+            self.outbound_queue.len(),
+            self.dead_letters.len()
+        )
+    }
+
+    // Inspection endpoint for messages that exhausted their retry budget.
+    #[http]
+    async fn get_dead_letters(&self, _request_body: String) -> String {
+        serde_json::to_string(&self.dead_letters).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    // EXAMPLE: AN ENDPOINT PROTECTED BY MIDDLEWARE LAYERS
+    // Composes RequestLoggingLayer + BearerAuthLayer + RateLimitLayer
+    // around a handful of state fields, instead of hand-rolling auth and
+    // rate-limit checks inline. Copy this `run_layers` wrapping pattern
+    // onto any handler you want the same protections on.
+    #[http]
+    async fn admin_stats(&mut self, request_body: String) -> Result<String, String> {
+        #[derive(Deserialize)]
+        struct AdminRequest {
+            bearer_token: String,
+        }
+        let req: AdminRequest = serde_json::from_str(&request_body)
+            .map_err(|e| format!("Invalid request: {}", e))?;
+
+        let logging_layer = RequestLoggingLayer;
+        let auth_layer = BearerAuthLayer::new("admin-secret-token".to_string());
+        // Shared across calls (see `admin_rate_limiter`) so its token
+        // bucket actually depletes and refills between requests.
+        let layers: Vec<&dyn Layer> = vec![&logging_layer, &auth_layer, admin_rate_limiter()];
+        let ctx = LayerContext {
+            endpoint: "admin_stats".to_string(),
+            caller: req.bearer_token,
+            started_at_secs: unix_now_secs(),
+        };
+
+        let counter = self.counter;
+        let message_count = self.messages.len();
+        let queue_len = self.outbound_queue.len();
+        run_layers(&layers, ctx, || async move {
+            Ok(format!(
+                "{{\"counter\":{},\"message_count\":{},\"queued_deliveries\":{}}}",
+                counter, message_count, queue_len
+            ))
+        })
+        .await
+    }
+
+    // Sets one role for one user. `role` arrives as a JSON string (the
+    // `#[wit_json]` path: see `wit_json_decode`) since `Role` carries data
+    // and can't cross the WIT boundary as a plain enum.
+    #[http]
+    async fn set_user_role(&mut self, request_body: String) -> Result<String, String> {
+        #[derive(Deserialize)]
+        struct SetRoleRequest {
+            user: String,
+            role_name: String,
+            role_json: String,
+        }
+        let req: SetRoleRequest = serde_json::from_str(&request_body)
+            .map_err(|e| format!("Invalid request: {}", e))?;
+        let role: Role = wit_json_decode(&req.role_json)?;
+
+        self.user_profiles
+            .entry(req.user.clone())
+            .or_insert_with(|| UserProfile {
+                name: req.user,
+                roles: HashMap::new(),
+            })
+            .roles
+            .insert(req.role_name, role);
+
+        Ok("Role updated".to_string())
+    }
+
+    // Returns one user's profile via the `#[wit_json]` path: the whole
+    // `UserProfile` (HashMap and all) travels as a single JSON string.
+    #[http]
+    async fn get_user_profile(&self, request_body: String) -> Result<String, String> {
+        let user: String =
+            serde_json::from_str(&request_body).map_err(|e| format!("Invalid request: {}", e))?;
+        let profile = self
+            .user_profiles
+            .get(&user)
+            .ok_or_else(|| format!("No profile for {}", user))?;
+        wit_json_encode(profile)
+    }
+
+    // Returns one user's roles via the WIT-native `list<tuple<K,V>>` path
+    // instead: no JSON string, just `Vec<(String, String)>` (each `Role`
+    // itself still needs the JSON-string treatment, since WIT has no
+    // native data-carrying enum - only the HashMap layer is native here).
+    #[http]
+    async fn get_user_roles_native(&self, request_body: String) -> Result<Vec<(String, String)>, String> {
+        let user: String =
+            serde_json::from_str(&request_body).map_err(|e| format!("Invalid request: {}", e))?;
+        let profile = self
+            .user_profiles
+            .get(&user)
+            .ok_or_else(|| format!("No profile for {}", user))?;
+
+        let role_strings: HashMap<String, String> = profile
+            .roles
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), wit_json_encode(v)?)))
+            .collect::<Result<_, String>>()?;
+
+        Ok(hashmap_to_wit_pairs(&role_strings))
+    }
+
+    // Counterpart to `get_user_roles_native`: takes the WIT-native
+    // `list<tuple<K,V>>` shape and reconstructs a `HashMap` in the
+    // handler via `wit_pairs_to_hashmap`, replacing the user's roles
+    // wholesale (each value is still a `#[wit_json]`-style JSON string,
+    // decoded back into a `Role` per entry).
+    #[http]
+    async fn set_user_roles_native(&mut self, request_body: String) -> Result<String, String> {
+        #[derive(Deserialize)]
+        struct SetRolesNativeRequest {
+            user: String,
+            roles: Vec<(String, String)>,
+        }
+        let req: SetRolesNativeRequest =
+            serde_json::from_str(&request_body).map_err(|e| format!("Invalid request: {}", e))?;
+
+        let role_strings = wit_pairs_to_hashmap(req.roles);
+        let roles: HashMap<String, Role> = role_strings
+            .into_iter()
+            .map(|(k, v)| Ok((k, wit_json_decode(&v)?)))
+            .collect::<Result<_, String>>()?;
+
+        self.user_profiles.insert(
+            req.user.clone(),
+            UserProfile {
+                name: req.user,
+                roles,
+            },
+        );
+
+        Ok("Roles updated".to_string())
+    }
+}
+
+// TYPED CLIENT PROXY FOR #[remote] METHODS
+// Calling `handle_remote_message` on another node today means hand-building
+// the `{ "MethodName": params }` envelope and discarding the typed response
+// (see `send_to_node` above) - easy to typo a method name or get the
+// tuple-vs-object encoding wrong.
+//
+// `AppStateClient` gives callers a typed method per `#[remote]` fn
+// instead: `AppStateClient::new(address).handle_remote_message(&sender, msg, &secret).await`.
+//
+// NOTE: in a full Hyperapp setup this struct would be generated by the
+// `#[hyperprocess]` macro itself, straight from the `#[remote]` fn
+// signatures on `AppState`, so it can never drift out of sync with them.
+// That codegen lives in the `hyperprocess_macro` proc-macro crate, which
+// isn't vendored into this skeleton - only the app crate is - so this is
+// hand-written here as the shape that macro would emit. If you add or
+// change a `#[remote]` method above, update the matching method below by
+// hand until that macro support lands.
+pub struct AppStateClient {
+    address: Address,
+}
+
+impl AppStateClient {
+    pub fn new(address: Address) -> Self {
+        Self { address }
+    }
+
+    // `handle_remote_message` expects a signed `SignedEnvelope` (see its
+    // doc comment), so this client signs `message` as `sender` before
+    // sending it rather than taking a raw string. `secret` must match
+    // the receiving node's `shared_signing_secret` (set via
+    // `set_signing_secret`).
+    pub async fn handle_remote_message(
+        &self,
+        sender: &str,
+        message: String,
+        secret: &[u8],
+    ) -> Result<String, String> {
+        let envelope = SignedEnvelope {
+            signature: sign_envelope(sender, &message, secret),
+            sender: sender.to_string(),
+            payload: message,
+        };
+        let request_wrapper = serde_json::json!({
+            "HandleRemoteMessage": serde_json::to_string(&envelope)
+                .map_err(|e| format!("Failed to encode envelope: {}", e))?
+        });
+        self.call(request_wrapper).await
+    }
+
+    // Shared send/await/deserialize path for the typed methods above.
+    // Generic over `T` so each typed method deserializes into its own
+    // `#[remote]` fn's declared `Result<T, String>` return type, rather
+    // than assuming every method returns a `String` (true of
+    // `handle_remote_message` today, but not a given for future methods).
+    async fn call<T: serde::de::DeserializeOwned>(
+        &self,
+        request_wrapper: serde_json::Value,
+    ) -> Result<T, String> {
+        let response = Request::new()
+            .target(self.address.clone())
+            .body(serde_json::to_vec(&request_wrapper).unwrap())
+            .expects_response(30)
+            .send_and_await_response(30)
+            .map_err(|e| format!("Failed to send message: {:?}", e))?;
+
+        serde_json::from_slice::<Result<T, String>>(response.body())
+            .map_err(|e| format!("Failed to decode response: {}", e))?
+    }
 }
 
 // ICON FOR YOUR APP (base64 encoded PNG, 256x256 recommended)
@@ -192,8 +1145,15 @@ const ICON: &str = "";
 // ❌ HashMap - use Vec<(K,V)> instead
 // ❌ Fixed arrays [T; N] - use Vec<T>
 // ❌ Complex enums with data
-// 
-// Workaround: Return complex data as JSON strings
+//
+// Workaround: Return complex data as JSON strings, or see
+// `wit_json_encode`/`wit_json_decode` and `hashmap_to_wit_pairs` /
+// `wit_pairs_to_hashmap` above for the two bridging paths in this file:
+// JSON-string fidelity for HashMap<_, rich enum>, or a native
+// `list<tuple<K,V>>` lowering when only the map (not its values) needs to
+// stay WIT-native. A `#[wit_json]` derive could do this encode/decode
+// automatically at the WIT boundary, but that's macro codegen this
+// skeleton crate doesn't vendor - see `set_user_role`/`get_user_profile`.
 
 // COMMON PATTERNS:
 
@@ -237,4 +1197,50 @@ const ICON: &str = "";
 //   * Missing _request_body parameter
 //   * Wrong parameter format (object vs tuple)
 //   * ProcessId parsing errors
-//   * Missing /our.js in HTML
\ No newline at end of file
+//   * Missing /our.js in HTML
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivery_backoff_doubles_then_caps() {
+        assert_eq!(delivery_backoff_secs(0), DELIVERY_BASE_BACKOFF_SECS);
+        assert_eq!(delivery_backoff_secs(1), DELIVERY_BASE_BACKOFF_SECS * 2);
+        assert_eq!(delivery_backoff_secs(2), DELIVERY_BASE_BACKOFF_SECS * 4);
+        assert_eq!(delivery_backoff_secs(20), DELIVERY_MAX_BACKOFF_SECS);
+    }
+
+    #[test]
+    fn envelope_round_trips_with_matching_secret() {
+        let secret = b"shared-secret";
+        let sig = sign_envelope("alice.os", "hello", secret);
+        assert!(verify_envelope("alice.os", "hello", &sig, secret));
+    }
+
+    #[test]
+    fn envelope_rejects_wrong_secret() {
+        let sig = sign_envelope("alice.os", "hello", b"secret-a");
+        assert!(!verify_envelope("alice.os", "hello", &sig, b"secret-b"));
+    }
+
+    #[test]
+    fn envelope_rejects_tampered_sender() {
+        let secret = b"shared-secret";
+        let sig = sign_envelope("alice.os", "hello", secret);
+        assert!(!verify_envelope("mallory.os", "hello", &sig, secret));
+    }
+
+    #[test]
+    fn envelope_rejects_tampered_payload() {
+        let secret = b"shared-secret";
+        let sig = sign_envelope("alice.os", "hello", secret);
+        assert!(!verify_envelope("alice.os", "goodbye", &sig, secret));
+    }
+
+    #[test]
+    fn envelope_rejects_malformed_signature() {
+        let secret = b"shared-secret";
+        assert!(!verify_envelope("alice.os", "hello", "not-hex!!", secret));
+    }
+}
\ No newline at end of file